@@ -0,0 +1,35 @@
+//! Types and utilities for file serving.
+//!
+//! * [`NamedFile`]: a [`Responder`](crate::response::Responder) for
+//!   streaming a single file, with range, conditional, and compression
+//!   support.
+//! * [`FileServer`]: a [`Handler`](crate::route::Handler) that mounts a
+//!   directory of static files, built on top of [`NamedFile`].
+//! * [`relative!`]: resolves a path relative to the compiled crate's
+//!   manifest directory, for locating bundled assets like `static/`.
+
+mod named_file;
+mod server;
+
+pub use named_file::{NamedFile, DispositionType};
+pub use server::{FileServer, Options};
+
+/// Retrieves the path of the directory containing the manifest of the
+/// invoking crate, joined with the specified path segments.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fs::relative;
+///
+/// let root = relative!("static");
+/// ```
+#[macro_export]
+macro_rules! relative {
+    ($path:expr) => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)
+    };
+}
+
+#[doc(inline)]
+pub use crate::relative;
@@ -1,12 +1,16 @@
 use std::io;
+use std::io::SeekFrom;
+use std::pin::Pin;
 use std::path::{Path, PathBuf};
 use std::ops::{Deref, DerefMut};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use tokio::fs::File;
+use tokio::io::{AsyncSeek, AsyncReadExt, BufReader};
 
 use crate::request::Request;
 use crate::response::{self, Responder};
-use crate::http::ContentType;
+use crate::http::{ContentType, Status};
 
 /// A [`Responder`] that sends file data with a Content-Type based on its
 /// file extension.
@@ -36,13 +40,68 @@ use crate::http::ContentType;
 /// pithier API.
 ///
 /// [`FileServer`]: crate::fs::FileServer
+///
+/// # Content-Disposition
+///
+/// By default, a [`NamedFile`] is served with an implicit `inline`
+/// disposition. Use [`NamedFile::set_content_disposition()`] to mark it as
+/// a [`DispositionType::Attachment`] so that browsers save the response
+/// instead of rendering it:
+///
+/// ```rust
+/// # use rocket::get;
+/// use rocket::fs::{NamedFile, DispositionType};
+///
+/// #[get("/report")]
+/// async fn report() -> Option<NamedFile> {
+///     let file = NamedFile::open("report.csv").await.ok()?;
+///     Some(file.set_content_disposition(DispositionType::Attachment))
+/// }
+/// ```
 #[derive(Debug)]
 pub struct NamedFile {
-    path: PathBuf, 
+    path: PathBuf,
     file: File,
-    /// If file ends in .gz, set `Content-Encoding` to gzip and use the base 
-    /// extension for `Content-Type`
-    compressed: bool,
+    /// The `Content-Encoding` to advertise, if `path` is actually a
+    /// precompressed sibling of the file that was requested (e.g. `gzip` for
+    /// a `path` of `foo.css.gz`). The matching extension is stripped from
+    /// `path` when deriving `Content-Type`.
+    encoding: Option<&'static str>,
+    /// Whether `encoding` was chosen by negotiating against an
+    /// `Accept-Encoding` header, in which case the response varies on it.
+    negotiated: bool,
+    /// The file's size, captured at open time so `Range` requests don't need
+    /// an extra `stat` and so the file can't appear to grow or shrink out
+    /// from under a seek.
+    len: u64,
+    /// The file's modification time, captured at open time and used to
+    /// derive an `ETag` and `Last-Modified` header.
+    modified: Option<SystemTime>,
+    /// Whether to serve this file `inline` or as an `attachment`.
+    disposition: DispositionType,
+    /// An override for the filename reported in `Content-Disposition`.
+    /// Defaults to `path`'s file name, minus any precompressed-encoding
+    /// extension.
+    disposition_filename: Option<String>,
+    /// The capacity of the buffer the file is read through when streaming
+    /// the response body. See [`NamedFile::with_buffer_size()`].
+    buffer_size: usize,
+}
+
+/// The default capacity of the buffer a [`NamedFile`]'s response body is
+/// streamed through, unless overridden with [`NamedFile::with_buffer_size()`].
+pub(crate) const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The `Content-Disposition` under which a [`NamedFile`] is served.
+///
+/// See [`NamedFile::set_content_disposition()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DispositionType {
+    /// Render the file in the browser. The default.
+    #[default]
+    Inline,
+    /// Prompt the browser to save the file instead of rendering it.
+    Attachment,
 }
 
 impl NamedFile {
@@ -66,19 +125,122 @@ impl NamedFile {
     /// }
     /// ```
     pub async fn open<P: AsRef<Path>>(path: P) -> io::Result<NamedFile> {
-        // FIXME: Grab the file size here and prohibit `seek`ing later (or else
-        // the file's effective size may change), to save on the cost of doing
-        // all of those `seek`s to determine the file size. But, what happens if
-        // the file gets changed between now and then?
-        let path = path.as_ref().to_path_buf();
-        let file = File::open(&path).await?;
-        Ok(NamedFile { path, file, compressed: false })
+        Self::open_with_encoding(path, None).await
     }
 
+    /// Opens `path`, which is assumed to be gzip-compressed, setting
+    /// `Content-Encoding: gzip` and deriving `Content-Type` from `path` with
+    /// its `.gz` extension stripped.
     pub async fn open_compressed<P: AsRef<Path>>(path: P) -> io::Result<NamedFile> {
+        Self::open_with_encoding(path, Some("gzip")).await
+    }
+
+    /// Opens `path`, negotiating a precompressed sibling against the
+    /// `accept_encoding` header value (as sent by the client in
+    /// `Accept-Encoding`) — e.g. for a request for `foo.css` this considers
+    /// `foo.css.br`, `foo.css.zst`, and `foo.css.gz`. Candidates are tried in
+    /// descending order of the client's quality value, ties broken by `br`,
+    /// `zstd`, `gzip` preference, honoring `identity;q=0`-style exclusions and
+    /// a `*` entry's quality value for tokens not listed explicitly; without
+    /// a `*` entry, unlisted tokens are not acceptable unless
+    /// `accept_encoding` is `None`, in which case all are. Falls back to `path` itself if no acceptable sibling exists. The
+    /// returned file's response always carries `Vary: Accept-Encoding`.
+    ///
+    /// Used by [`FileServer`]'s [`Options::PreZipped`].
+    ///
+    /// [`FileServer`]: crate::fs::FileServer
+    /// [`Options::PreZipped`]: crate::fs::Options::PreZipped
+    pub async fn open_negotiated<P: AsRef<Path>>(
+        path: P,
+        accept_encoding: Option<&str>,
+    ) -> io::Result<NamedFile> {
+        const ENCODINGS: [&str; 3] = ["br", "zstd", "gzip"];
+
+        let path = path.as_ref();
+        let acceptable = parse_accept_encoding(accept_encoding.unwrap_or(""));
+        let qvalue = |token: &str| acceptable.iter()
+            .find(|(enc, _)| *enc == token)
+            .or_else(|| acceptable.iter().find(|(enc, _)| *enc == "*"))
+            .map(|(_, q)| *q)
+            // With no header at all every encoding is acceptable; otherwise
+            // only those listed (or covered by `*`) are.
+            .unwrap_or(if accept_encoding.is_none() { 1.0 } else { 0.0 });
+
+        // Sort by descending quality value; `sort_by` is stable, so ties
+        // keep the `br`, `zstd`, `gzip` order declared in `ENCODINGS`.
+        let mut candidates: Vec<&str> = ENCODINGS.into_iter().filter(|t| qvalue(t) > 0.0).collect();
+        candidates.sort_by(|a, b| {
+            qvalue(b).partial_cmp(&qvalue(a)).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for token in candidates {
+            let sibling = append_extension(path, encoding_extension(token).unwrap());
+            if let Ok(mut file) = Self::open_with_encoding(&sibling, Some(token)).await {
+                file.negotiated = true;
+                return Ok(file);
+            }
+        }
+
+        let mut file = Self::open(path).await?;
+        file.negotiated = true;
+        Ok(file)
+    }
+
+    async fn open_with_encoding<P: AsRef<Path>>(
+        path: P,
+        encoding: Option<&'static str>,
+    ) -> io::Result<NamedFile> {
         let path = path.as_ref().to_path_buf();
         let file = File::open(&path).await?;
-        Ok(NamedFile { path, file, compressed: true })
+        let metadata = file.metadata().await?;
+        let (len, modified) = (metadata.len(), metadata.modified().ok());
+        Ok(NamedFile {
+            path, file, encoding, negotiated: false, len, modified,
+            disposition: DispositionType::Inline,
+            disposition_filename: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        })
+    }
+
+    /// Sets the capacity of the buffer the file is read through when
+    /// streaming the response body, in bytes. Defaults to 64 KiB. Larger
+    /// values trade memory for fewer, larger reads off the underlying file,
+    /// which can improve throughput for very large files on fast links.
+    ///
+    /// A corresponding setter exists on [`FileServer`] to apply this to
+    /// every file it serves.
+    ///
+    /// [`FileServer`]: crate::fs::FileServer
+    pub fn with_buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size.max(1);
+        self
+    }
+
+    /// Sets whether this file is served `inline` (the default) or as an
+    /// `attachment`, prompting the browser to save it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::{NamedFile, DispositionType};
+    ///
+    /// # async fn f() -> std::io::Result<()> {
+    /// let file = NamedFile::open("report.csv").await?
+    ///     .set_content_disposition(DispositionType::Attachment);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_content_disposition(mut self, disposition: DispositionType) -> Self {
+        self.disposition = disposition;
+        self
+    }
+
+    /// Overrides the filename reported in `Content-Disposition`. Without
+    /// this, the file's own name (the last component of [`Self::path()`],
+    /// minus the extension of a precompressed sibling such as `.gz`) is used.
+    pub fn set_filename(mut self, filename: impl Into<String>) -> Self {
+        self.disposition_filename = Some(filename.into());
+        self
     }
 
     /// Retrieve the underlying `File`.
@@ -154,21 +316,354 @@ impl NamedFile {
     }
 }
 
+/// A single, inclusive byte range `start..=end` resolved against a known
+/// file length.
+#[derive(Debug, Clone, Copy)]
+struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+/// The result of interpreting a `Range` request header against a file of a
+/// known length.
+#[derive(Debug)]
+enum RangeRequest {
+    /// A single range that can be served as `206 Partial Content`.
+    Satisfiable(ByteRange),
+    /// The header named a range, but none of it overlaps the file.
+    Unsatisfiable,
+}
+
+/// Parses a `Range: bytes=...` header value into a [`RangeRequest`].
+///
+/// Returns `None` if the header doesn't name a byte-range-spec we support
+/// (for example, a multi-range request), in which case the request should be
+/// treated as if no `Range` header were present.
+fn parse_byte_range(header: &str, len: u64) -> Option<RangeRequest> {
+    let spec = header.strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        // Multiple ranges aren't supported; fall back to a full response.
+        return None;
+    }
+
+    let (raw_start, raw_end) = spec.split_once('-')?;
+    if raw_start.is_empty() && raw_end.is_empty() {
+        return None;
+    }
+
+    let range = if raw_start.is_empty() {
+        // `-suffixlen`: the last `raw_end` bytes of the file.
+        let suffix_len: u64 = raw_end.parse().ok()?;
+        if suffix_len == 0 || len == 0 {
+            return Some(RangeRequest::Unsatisfiable);
+        }
+
+        ByteRange { start: len.saturating_sub(suffix_len), end: len - 1 }
+    } else {
+        let start: u64 = raw_start.parse().ok()?;
+        let end = match raw_end.is_empty() {
+            true => len.saturating_sub(1),
+            false => raw_end.parse().ok()?,
+        };
+
+        if len == 0 || start >= len || start > end {
+            return Some(RangeRequest::Unsatisfiable);
+        }
+
+        ByteRange { start, end: std::cmp::min(end, len - 1) }
+    };
+
+    Some(RangeRequest::Satisfiable(range))
+}
+
+/// Maps a `Content-Encoding` token to the on-disk extension used for its
+/// precompressed sibling files, e.g. `"gzip"` -> `"gz"`.
+fn encoding_extension(token: &str) -> Option<&'static str> {
+    match token {
+        "br" => Some("br"),
+        "zstd" => Some("zst"),
+        "gzip" => Some("gz"),
+        _ => None,
+    }
+}
+
+/// Appends an additional extension to `path`, e.g. `foo.css` + `br` ->
+/// `foo.css.br`.
+fn append_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
+
+/// Parses an `Accept-Encoding` header into `(token, qvalue)` pairs, in the
+/// order they appear, treating a missing `q` parameter as `q=1`.
+fn parse_accept_encoding(header: &str) -> Vec<(&str, f32)> {
+    header.split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let token = parts.next()?.trim();
+            if token.is_empty() {
+                return None;
+            }
+
+            let q = parts.find_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                (name.trim() == "q").then(|| value.trim().parse().ok()).flatten()
+            }).unwrap_or(1.0);
+
+            Some((token, q))
+        })
+        .collect()
+}
+
+/// Formats a [`SystemTime`] as an RFC 7231 `HTTP-date`
+/// (e.g. `Tue, 15 Nov 1994 08:12:31 GMT`), truncated to whole seconds.
+fn http_date(time: SystemTime) -> Option<String> {
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun",
+        "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    let (days, secs_of_day) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    let (hour, minute, second) = (secs_of_day / 3600, (secs_of_day / 60) % 60, secs_of_day % 60);
+
+    // Howard Hinnant's `civil_from_days`: days-since-epoch -> proleptic Gregorian y/m/d.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    let weekday = WEEKDAYS[(days.rem_euclid(7) as usize + 4) % 7];
+    let month_name = MONTHS[(month - 1) as usize];
+
+    Some(format!("{weekday}, {day:02} {month_name} {year} {hour:02}:{minute:02}:{second:02} GMT"))
+}
+
+/// Derives a weak `ETag` from a modification time and file length, e.g.
+/// `W/"64b1f2a0-1f4"`.
+fn weak_etag(modified: Option<SystemTime>, len: u64) -> Option<String> {
+    let mtime_secs = modified?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(format!("W/\"{mtime_secs:x}-{len:x}\""))
+}
+
+/// Checks a validator header (`If-None-Match` or `If-Range`) for a weak match
+/// against `etag`, per the rules for `W/`-prefixed comparison.
+fn etag_matches(header: &str, etag: &str) -> bool {
+    if header.trim() == "*" {
+        return true;
+    }
+
+    let etag = etag.trim_start_matches("W/");
+    header.split(',').any(|candidate| candidate.trim().trim_start_matches("W/") == etag)
+}
+
+/// Builds a `Content-Disposition` header value for `filename`, quoting and
+/// escaping it for the `filename` parameter and adding an RFC 5987
+/// `filename*=UTF-8''...` fallback when `filename` isn't ASCII.
+fn content_disposition(disposition: DispositionType, filename: &str) -> String {
+    let kind = match disposition {
+        DispositionType::Inline => "inline",
+        DispositionType::Attachment => "attachment",
+    };
+
+    if filename.is_ascii() {
+        return format!("{kind}; filename=\"{}\"", quote_disposition_filename(filename));
+    }
+
+    let ascii_fallback: String = filename.chars()
+        .map(|c| if c.is_ascii() { c } else { '_' })
+        .collect();
+
+    format!(
+        "{kind}; filename=\"{}\"; filename*=UTF-8''{}",
+        quote_disposition_filename(&ascii_fallback),
+        percent_encode_rfc5987(filename),
+    )
+}
+
+/// Escapes `\` and `"` for use inside an HTTP quoted-string, and replaces
+/// control characters (including `CR`/`LF`) with `_` so a crafted filename
+/// can't smuggle extra header lines into the response.
+fn quote_disposition_filename(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            c if c.is_control() => out.push('_'),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encodes `s` per RFC 5987's `attr-char` for use in `filename*`.
+fn percent_encode_rfc5987(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(*byte as char);
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
 /// Streams the named file to the client. Sets or overrides the Content-Type in
 /// the response according to the file's extension if the extension is
 /// recognized. See [`ContentType::from_extension()`] for more information. If
 /// you would like to stream a file with a different Content-Type than that
 /// implied by its extension, use a [`File`] directly.
+///
+/// If the request carries a `Range` header, a single satisfiable byte range
+/// is served as `206 Partial Content` with a `Content-Range` header; a range
+/// that doesn't overlap the file results in `416 Range Not Satisfiable`.
+/// Every `200 OK` and `206 Partial Content` response advertises
+/// `Accept-Ranges: bytes`; `304` and `416` responses don't carry it.
+///
+/// A weak `ETag` and `Last-Modified` header are derived from the file's
+/// metadata. If `If-None-Match` matches the `ETag`, or, absent
+/// `If-None-Match`, `If-Modified-Since` equals the `Last-Modified` value
+/// exactly, the response short-circuits to `304 Not Modified` with no body.
+/// `If-Modified-Since` dates are not parsed, so any other date yields the
+/// full response. A `Range` request that carries an `If-Range` validator is
+/// only honored if the validator matches; otherwise the full file is served
+/// with `200 OK`.
+///
+/// A file opened with [`NamedFile::open_negotiated()`] carries `Vary:
+/// Accept-Encoding` and `Content-Encoding` for whichever precompressed
+/// sibling was chosen.
+///
+/// The body is read through a buffer sized by [`NamedFile::with_buffer_size()`]
+/// rather than through the default [`File`] responder.
 impl<'r> Responder<'r, 'static> for NamedFile {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
-        let mut response = self.file.respond_to(req)?;
-        if let Some(mut ext) = self.path.extension() {
-            let stripped = self.path.with_extension("");
-
-            if self.compressed && ext == std::ffi::OsStr::new("gz") {
-                response.set_raw_header("Content-Encoding", "gzip");
-                if let Some(orig_ext) = stripped.extension() {
-                    ext = orig_ext; // override extension-based content type
+        let NamedFile {
+            path, file, encoding, negotiated, len, modified,
+            disposition, disposition_filename, buffer_size,
+        } = self;
+
+        let mut reader = BufReader::with_capacity(buffer_size, file);
+
+        let etag = weak_etag(modified, len);
+        let last_modified = modified.and_then(http_date);
+
+        let if_none_match = req.headers().get_one("If-None-Match");
+
+        // Per RFC 7232 §3.3, `If-Modified-Since` is only considered when the
+        // request has no `If-None-Match`; the (weak) `ETag` comparison wins.
+        // `If-Modified-Since` matches only if it equals `Last-Modified` exactly.
+        let not_modified = match if_none_match {
+            Some(header) => etag.as_deref().is_some_and(|etag| etag_matches(header, etag)),
+            None => last_modified.as_deref()
+                .zip(req.headers().get_one("If-Modified-Since"))
+                .is_some_and(|(date, header)| header.trim() == date),
+        };
+
+        if not_modified {
+            let mut response = response::Response::build()
+                .status(Status::NotModified)
+                .finalize();
+
+            if let Some(etag) = &etag {
+                response.set_raw_header("ETag", etag.clone());
+            }
+            if let Some(date) = &last_modified {
+                response.set_raw_header("Last-Modified", date.clone());
+            }
+
+            return Ok(response);
+        }
+
+        // A `Range` request is only honored if it's paired with a matching
+        // `If-Range` validator, or no `If-Range` was given at all.
+        let if_range_ok = req.headers().get_one("If-Range").is_none_or(|validator| {
+            etag.as_deref().is_some_and(|etag| etag_matches(validator, etag))
+                || last_modified.as_deref().is_some_and(|date| validator.trim() == date)
+        });
+
+        let range = req.headers().get_one("Range")
+            .filter(|_| if_range_ok)
+            .and_then(|header| parse_byte_range(header, len));
+
+        let mut response = match range {
+            Some(RangeRequest::Unsatisfiable) => {
+                return Ok(response::Response::build()
+                    .status(Status::RangeNotSatisfiable)
+                    .raw_header("Content-Range", format!("bytes */{len}"))
+                    .finalize());
+            }
+            Some(RangeRequest::Satisfiable(range)) => {
+                let body_len = range.end - range.start + 1;
+                // Seek the `File` itself: `BufReader::start_seek` only records
+                // the target, leaving the seek to a `poll_complete` never made.
+                Pin::new(reader.get_mut()).start_seek(SeekFrom::Start(range.start))
+                    .map_err(|_| Status::InternalServerError)?;
+
+                response::Response::build()
+                    .status(Status::PartialContent)
+                    .raw_header("Content-Range",
+                        format!("bytes {}-{}/{len}", range.start, range.end))
+                    .raw_header("Content-Length", body_len.to_string())
+                    .streamed_body(reader.take(body_len))
+                    .finalize()
+            }
+            None => {
+                response::Response::build()
+                    .status(Status::Ok)
+                    .raw_header("Content-Length", len.to_string())
+                    .streamed_body(reader)
+                    .finalize()
+            }
+        };
+
+        response.set_raw_header("Accept-Ranges", "bytes");
+
+        if let Some(etag) = &etag {
+            response.set_raw_header("ETag", etag.clone());
+        }
+        if let Some(date) = &last_modified {
+            response.set_raw_header("Last-Modified", date.clone());
+        }
+        if negotiated {
+            response.set_raw_header("Vary", "Accept-Encoding");
+        }
+        // If `path` is actually a precompressed sibling (e.g. `report.csv.gz`
+        // for a negotiated `gzip` encoding), `stripped` is the logical
+        // resource path (`report.csv`) it stands in for.
+        let matches_encoding_suffix = encoding.zip(path.extension())
+            .is_some_and(|(token, ext)| {
+                encoding_extension(token).is_some_and(|suffix| ext == std::ffi::OsStr::new(suffix))
+            });
+        let stripped = match matches_encoding_suffix {
+            true => path.with_extension(""),
+            false => path.clone(),
+        };
+
+        if disposition == DispositionType::Attachment {
+            let filename = disposition_filename.unwrap_or_else(|| {
+                stripped.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+            });
+            response.set_raw_header("Content-Disposition", content_disposition(disposition, &filename));
+        }
+
+        if let Some(mut ext) = path.extension() {
+            if let Some(token) = encoding {
+                response.set_raw_header("Content-Encoding", token);
+                if matches_encoding_suffix {
+                    if let Some(orig_ext) = stripped.extension() {
+                        ext = orig_ext; // override extension-based content type
+                    }
                 }
             }
             if let Some(ct) = ContentType::from_extension(&ext.to_string_lossy()) {
@@ -180,6 +675,84 @@ impl<'r> Responder<'r, 'static> for NamedFile {
     }
 }
 
+/// Renders an HTML directory listing for `dir`, one link per entry.
+///
+/// Entries are sorted directories-first, then alphabetically by name. Each
+/// link's `href` is percent-encoded and its display text is HTML-escaped;
+/// subdirectories get a trailing slash on both. Dotfiles are included only
+/// if `show_hidden` is set.
+///
+/// Used by [`FileServer`]'s [`Options::ShowListing`] when a directory
+/// request finds no index file to serve.
+///
+/// [`FileServer`]: crate::fs::FileServer
+/// [`Options::ShowListing`]: crate::fs::Options::ShowListing
+pub(crate) async fn render_listing(dir: &Path, show_hidden: bool) -> io::Result<String> {
+    let mut entries = vec![];
+    let mut read_dir = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = read_dir.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !show_hidden && name.starts_with('.') {
+            continue;
+        }
+
+        let is_dir = entry.file_type().await?.is_dir();
+        entries.push((name, is_dir));
+    }
+
+    entries.sort_by(|(a_name, a_dir), (b_name, b_dir)| {
+        b_dir.cmp(a_dir).then_with(|| a_name.cmp(b_name))
+    });
+
+    let mut page = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"></head><body>\n<ul>\n"
+    );
+
+    for (name, is_dir) in entries {
+        let mut href = percent_encode_path_segment(&name);
+        let mut text = html_escape(&name);
+        if is_dir {
+            href.push('/');
+            text.push('/');
+        }
+
+        page.push_str(&format!("<li><a href=\"{href}\">{text}</a></li>\n"));
+    }
+
+    page.push_str("</ul>\n</body></html>\n");
+    Ok(page)
+}
+
+/// Escapes `s` for safe inclusion in HTML text content.
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encodes a single path segment for use in an `href`.
+fn percent_encode_path_segment(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char);
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
 impl Deref for NamedFile {
     type Target = File;
 
@@ -193,3 +766,172 @@ impl DerefMut for NamedFile {
         &mut self.file
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sat(header: &str, len: u64) -> ByteRange {
+        match parse_byte_range(header, len) {
+            Some(RangeRequest::Satisfiable(range)) => range,
+            other => panic!("expected a satisfiable range, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn byte_range_start_end() {
+        let range = sat("bytes=0-499", 1000);
+        assert_eq!((range.start, range.end), (0, 499));
+    }
+
+    #[test]
+    fn byte_range_start_only() {
+        let range = sat("bytes=500-", 1000);
+        assert_eq!((range.start, range.end), (500, 999));
+    }
+
+    #[test]
+    fn byte_range_suffix() {
+        let range = sat("bytes=-200", 1000);
+        assert_eq!((range.start, range.end), (800, 999));
+    }
+
+    #[test]
+    fn byte_range_end_clamped_to_len() {
+        let range = sat("bytes=0-9999", 1000);
+        assert_eq!((range.start, range.end), (0, 999));
+    }
+
+    #[test]
+    fn byte_range_start_past_len_is_unsatisfiable() {
+        assert!(matches!(
+            parse_byte_range("bytes=1000-1999", 1000),
+            Some(RangeRequest::Unsatisfiable)
+        ));
+    }
+
+    #[test]
+    fn byte_range_empty_file_is_unsatisfiable() {
+        assert!(matches!(
+            parse_byte_range("bytes=0-10", 0),
+            Some(RangeRequest::Unsatisfiable)
+        ));
+    }
+
+    #[test]
+    fn byte_range_rejects_multi_range() {
+        assert!(parse_byte_range("bytes=0-10,20-30", 1000).is_none());
+    }
+
+    #[test]
+    fn byte_range_rejects_missing_unit() {
+        assert!(parse_byte_range("0-10", 1000).is_none());
+    }
+
+    #[test]
+    fn byte_range_rejects_empty_spec() {
+        assert!(parse_byte_range("bytes=-", 1000).is_none());
+    }
+
+    #[test]
+    fn http_date_epoch() {
+        assert_eq!(
+            http_date(UNIX_EPOCH).as_deref(),
+            Some("Thu, 01 Jan 1970 00:00:00 GMT")
+        );
+    }
+
+    #[test]
+    fn http_date_known_instant() {
+        // 1994-11-06T08:49:37Z, the example date from RFC 7231 §7.1.1.1.
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(784111777);
+        assert_eq!(
+            http_date(time).as_deref(),
+            Some("Sun, 06 Nov 1994 08:49:37 GMT")
+        );
+    }
+
+    #[test]
+    fn http_date_leap_day() {
+        // 2000-02-29T00:00:00Z.
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(951782400);
+        assert_eq!(
+            http_date(time).as_deref(),
+            Some("Tue, 29 Feb 2000 00:00:00 GMT")
+        );
+    }
+
+    #[test]
+    fn http_date_truncates_sub_second() {
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(500);
+        assert_eq!(
+            http_date(time).as_deref(),
+            Some("Thu, 01 Jan 1970 00:00:00 GMT")
+        );
+    }
+
+    #[test]
+    fn etag_matches_exact() {
+        assert!(etag_matches("\"abc\"", "\"abc\""));
+    }
+
+    #[test]
+    fn etag_matches_weak_prefix_either_side() {
+        assert!(etag_matches("W/\"abc\"", "\"abc\""));
+        assert!(etag_matches("\"abc\"", "W/\"abc\""));
+    }
+
+    #[test]
+    fn etag_matches_wildcard() {
+        assert!(etag_matches("*", "\"anything\""));
+    }
+
+    #[test]
+    fn etag_matches_one_of_list() {
+        assert!(etag_matches("\"a\", \"b\", \"c\"", "\"b\""));
+    }
+
+    #[test]
+    fn etag_matches_rejects_mismatch() {
+        assert!(!etag_matches("\"a\", \"b\"", "\"c\""));
+    }
+
+    #[test]
+    fn content_disposition_inline_ascii() {
+        let value = content_disposition(DispositionType::Inline, "report.csv");
+        assert_eq!(value, "inline; filename=\"report.csv\"");
+    }
+
+    #[test]
+    fn content_disposition_attachment_ascii() {
+        let value = content_disposition(DispositionType::Attachment, "report.csv");
+        assert_eq!(value, "attachment; filename=\"report.csv\"");
+    }
+
+    #[test]
+    fn content_disposition_escapes_quotes_and_backslashes() {
+        let value = content_disposition(DispositionType::Attachment, "we\"ird\\name.txt");
+        assert_eq!(value, "attachment; filename=\"we\\\"ird\\\\name.txt\"");
+    }
+
+    #[test]
+    fn content_disposition_non_ascii_adds_rfc5987_fallback() {
+        let value = content_disposition(DispositionType::Attachment, "café.txt");
+        assert_eq!(value, "attachment; filename=\"caf_.txt\"; filename*=UTF-8''caf%C3%A9.txt");
+    }
+
+    #[test]
+    fn content_disposition_strips_crlf_from_ascii_filename() {
+        // A filename that's pure ASCII bypasses the RFC 5987 fallback, so
+        // `quote_disposition_filename` must neutralize control characters on
+        // its own or this would smuggle a header line into the response.
+        let value = content_disposition(
+            DispositionType::Attachment,
+            "evil\r\nSet-Cookie: x=y",
+        );
+
+        assert!(!value.contains('\r'));
+        assert!(!value.contains('\n'));
+        assert_eq!(value, "attachment; filename=\"evil__Set-Cookie: x=y\"");
+    }
+}
@@ -0,0 +1,196 @@
+use std::ops::BitOr;
+use std::path::{Path, PathBuf};
+
+use crate::request::Request;
+use crate::data::Data;
+use crate::http::Method;
+use crate::route::{Route, Handler, Outcome};
+use crate::response::Redirect;
+
+use super::NamedFile;
+use super::named_file::{render_listing, DEFAULT_BUFFER_SIZE};
+
+/// Toggles behavior of [`FileServer`].
+///
+/// Combine options with the `|` operator, e.g. `Options::Index |
+/// Options::DotFiles`.
+///
+/// | Option                     | Description                                                            | Default? |
+/// |-----------------------------|-------------------------------------------------------------------------|----------|
+/// | [`Options::None`]           | Don't allow any of the other options.                                  | **Yes**  |
+/// | [`Options::DotFiles`]       | Allow serving dotfiles.                                                 | No       |
+/// | [`Options::Index`]         | Serve an `index.html` file for a directory request.                     | No       |
+/// | [`Options::NormalizeDirs`] | Redirect a directory request missing a trailing slash to one with it.   | No       |
+/// | [`Options::PreZipped`]     | Negotiate a precompressed sibling via `Accept-Encoding`.                | No       |
+/// | [`Options::ShowListing`]   | Render an HTML directory listing when no index file can be served.     | No       |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options(u8);
+
+impl Options {
+    /// Don't allow any of the other options. This is different from `Options::empty()`, which
+    /// isn't provided, in that the latter would imply that default options are not allowed.
+    pub const None: Options = Options(0b0000);
+
+    /// Allow serving files with a name that starts with a `.`, which are otherwise hidden.
+    pub const DotFiles: Options = Options(0b0001);
+
+    /// Serve an `index.html` file for a directory request, if one exists.
+    pub const Index: Options = Options(0b0010);
+
+    /// Redirect a directory request that is missing a trailing slash to one that has it.
+    pub const NormalizeDirs: Options = Options(0b0100);
+
+    /// Negotiate a precompressed sibling file via [`NamedFile::open_negotiated()`].
+    pub const PreZipped: Options = Options(0b1000);
+
+    /// Render an HTML directory listing when a directory is requested and no
+    /// index file can be served.
+    pub const ShowListing: Options = Options(0b10000);
+
+    fn contains(self, other: Options) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl BitOr for Options {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Options(self.0 | rhs.0)
+    }
+}
+
+/// A [`Handler`] that serves files from a directory.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fs::{FileServer, relative};
+///
+/// let server = FileServer::from(relative!("static"));
+/// ```
+///
+/// This [`Handler`] can then be [mounted](rocket::Rocket::mount()) like any other:
+///
+/// ```rust
+/// # use rocket::fs::{FileServer, relative};
+/// # let server = FileServer::from(relative!("static"));
+/// rocket::build().mount("/public", server);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileServer {
+    root: PathBuf,
+    options: Options,
+    rank: isize,
+    buffer_size: usize,
+}
+
+impl FileServer {
+    /// The default rank use by `FileServer` routes.
+    const DEFAULT_RANK: isize = 10;
+
+    /// Constructs a new `FileServer` that serves files from `path` with
+    /// `options` enabled.
+    pub fn new<P: AsRef<Path>>(path: P, options: Options) -> Self {
+        FileServer {
+            root: path.as_ref().into(),
+            options,
+            rank: Self::DEFAULT_RANK,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+
+    /// Sets the rank for the generated routes to `rank`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Sets the buffer size used to stream each served file's body, in
+    /// bytes. Applies [`NamedFile::with_buffer_size()`] to every file `self`
+    /// serves; see its docs for details. Defaults to 64 KiB.
+    pub fn with_buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size.max(1);
+        self
+    }
+}
+
+impl<P: AsRef<Path>> From<P> for FileServer {
+    /// Constructs a new `FileServer` that serves files from `path` with
+    /// [`Options::Index`] enabled.
+    fn from(path: P) -> Self {
+        FileServer::new(path, Options::Index)
+    }
+}
+
+#[crate::async_trait]
+impl Handler for FileServer {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        use crate::http::uri::fmt::Path as UriPath;
+        use crate::http::Status;
+
+        let path = req.segments::<crate::http::uri::Segments<'_, UriPath>>(0..)
+            .ok()
+            .and_then(|segments| {
+                segments.to_path_buf(self.options.contains(Options::DotFiles)).ok()
+            });
+
+        let path = match path {
+            Some(path) => self.root.join(path),
+            None => return Outcome::forward(data, Status::NotFound),
+        };
+
+        if path.is_dir() {
+            // `ShowListing` renders hrefs relative to the directory itself, so
+            // a request missing the trailing slash must be normalized first
+            // or every link on the page would resolve one level too high.
+            let must_normalize = self.options.contains(Options::NormalizeDirs)
+                || self.options.contains(Options::ShowListing);
+
+            if must_normalize && !req.uri().path().ends_with('/') {
+                let mut normalized = req.uri().clone().into_owned();
+                normalized.set_path(format!("{}/", normalized.path()));
+                return Outcome::from(req, Redirect::permanent(normalized.to_string()));
+            }
+
+            if self.options.contains(Options::Index) {
+                let index = path.join("index.html");
+                if index.is_file() {
+                    return match NamedFile::open(&index).await {
+                        Ok(file) => Outcome::from(req, file.with_buffer_size(self.buffer_size)),
+                        Err(_) => Outcome::forward(data, Status::NotFound),
+                    };
+                }
+            }
+
+            if self.options.contains(Options::ShowListing) {
+                let show_hidden = self.options.contains(Options::DotFiles);
+                return match render_listing(&path, show_hidden).await {
+                    Ok(listing) => Outcome::from(req, (crate::http::ContentType::HTML, listing)),
+                    Err(_) => Outcome::forward(data, Status::NotFound),
+                };
+            }
+
+            return Outcome::forward(data, Status::NotFound);
+        }
+
+        let accept_encoding = req.headers().get_one("Accept-Encoding");
+        let file = if self.options.contains(Options::PreZipped) {
+            NamedFile::open_negotiated(&path, accept_encoding).await
+        } else {
+            NamedFile::open(&path).await
+        };
+
+        match file {
+            Ok(file) => Outcome::from(req, file.with_buffer_size(self.buffer_size)),
+            Err(_) => Outcome::forward(data, Status::NotFound),
+        }
+    }
+}
+
+impl From<FileServer> for Vec<Route> {
+    fn from(server: FileServer) -> Self {
+        let rank = server.rank;
+        vec![Route::ranked(rank, Method::Get, "/<path..>", server)]
+    }
+}
@@ -22,6 +22,7 @@ fn rocket() -> Rocket<Build> {
         .mount("/redir", FileServer::new(&root, Options::NormalizeDirs))
         .mount("/redir_index", FileServer::new(&root, Options::NormalizeDirs | Options::Index))
         .mount("/compressed", FileServer::new(&root, Options::PreZipped))
+        .mount("/listing", FileServer::new(&root, Options::ShowListing))
 }
 
 static REGULAR_FILES: &[&str] = &[
@@ -213,6 +214,131 @@ fn test_redirection() {
     assert_eq!(response.headers().get("Location").next(), Some("/redir_index/other/"));
 }
 
+#[test]
+fn test_listing() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+
+    // `ShowListing` renders hrefs relative to the directory, so a request
+    // missing the trailing slash is redirected to add it first -- otherwise
+    // every link on the rendered page would resolve one level too high.
+    let response = client.get("/listing/other").dispatch();
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get("Location").next(), Some("/listing/other/"));
+
+    // A directory with no index file gets rendered as a listing.
+    let response = client.get("/listing/other/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_string().expect("response body");
+    assert!(body.contains("hello.txt"));
+    assert!(!body.contains(".hidden"));
+
+    // Without Index, a directory that *does* have an index file still
+    // falls back to the listing.
+    let response = client.get("/listing/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.into_string().expect("response body").contains("index.html"));
+
+    // Files are served normally alongside the listing behavior.
+    assert_all(&client, "listing", REGULAR_FILES, true);
+}
+
+#[test]
+fn test_range_request() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let full = std::fs::read(static_root().join("other/hello.txt")).expect("read fixture");
+
+    let mut request = client.get("/no_index/other/hello.txt");
+    request.add_header(Header::new("Range", "bytes=0-4"));
+    let mut response = request.dispatch();
+    assert_eq!(response.status(), Status::PartialContent);
+    assert_eq!(
+        response.headers().get_one("Content-Range"),
+        Some(format!("bytes 0-4/{}", full.len())).as_deref()
+    );
+
+    let mut body = vec![];
+    response.read_to_end(&mut body).expect("read response");
+    assert_eq!(body, full[0..5]);
+}
+
+#[test]
+fn test_range_request_unsatisfiable() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let len = std::fs::metadata(static_root().join("other/hello.txt")).expect("stat fixture").len();
+
+    let mut request = client.get("/no_index/other/hello.txt");
+    request.add_header(Header::new("Range", format!("bytes={}-", len + 10)));
+    let response = request.dispatch();
+    assert_eq!(response.status(), Status::RangeNotSatisfiable);
+}
+
+#[test]
+fn test_conditional_request_if_none_match() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+
+    let response = client.get("/no_index/other/hello.txt").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").expect("ETag header").to_owned();
+
+    let mut request = client.get("/no_index/other/hello.txt");
+    request.add_header(Header::new("If-None-Match", etag));
+    let response = request.dispatch();
+    assert_eq!(response.status(), Status::NotModified);
+}
+
+#[test]
+fn test_conditional_request_if_modified_since() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+
+    let response = client.get("/no_index/other/hello.txt").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let last_modified = response.headers().get_one("Last-Modified").expect("Last-Modified header").to_owned();
+
+    let mut request = client.get("/no_index/other/hello.txt");
+    request.add_header(Header::new("If-Modified-Since", last_modified));
+    let response = request.dispatch();
+    assert_eq!(response.status(), Status::NotModified);
+}
+
+#[test]
+fn test_conditional_request_if_none_match_takes_precedence() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+
+    let response = client.get("/no_index/other/hello.txt").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let last_modified = response.headers().get_one("Last-Modified").expect("Last-Modified header").to_owned();
+
+    // Per RFC 7232 §3.3, a stale `If-None-Match` must be honored even if
+    // `If-Modified-Since` would otherwise indicate the file is unchanged.
+    let mut request = client.get("/no_index/other/hello.txt");
+    request.add_header(Header::new("If-None-Match", "\"not-the-real-etag\""));
+    request.add_header(Header::new("If-Modified-Since", last_modified));
+    let response = request.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn test_attachment_disposition() {
+    use rocket::{get, routes};
+    use rocket::fs::{NamedFile, DispositionType};
+
+    #[get("/download")]
+    async fn download() -> Option<NamedFile> {
+        let file = NamedFile::open(static_root().join("other/hello.txt")).await.ok()?;
+        Some(file.set_content_disposition(DispositionType::Attachment))
+    }
+
+    let rocket = rocket().mount("/", routes![download]);
+    let client = Client::debug(rocket).expect("valid rocket");
+
+    let response = client.get("/download").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Content-Disposition"),
+        Some("attachment; filename=\"hello.txt\"")
+    );
+}
+
 #[test]
 fn test_compression() {
     let client = Client::debug(rocket()).expect("valid rocket");
@@ -220,3 +346,43 @@ fn test_compression() {
         assert_file(&client, "compressed", path, true, true)
     }
 }
+
+#[test]
+fn test_compression_negotiation() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+
+    // (Accept-Encoding header to send, or `None` to omit it entirely,
+    // expected Content-Encoding, or `None` for an uncompressed response).
+    let cases: &[(Option<&str>, Option<&str>)] = &[
+        // A single acceptable encoding is selected directly.
+        (Some("br"), Some("br")),
+        (Some("zstd"), Some("zstd")),
+        (Some("gzip"), Some("gzip")),
+        // Several at equal quality fall back to the br, zstd, gzip order.
+        (Some("br, gzip"), Some("br")),
+        (Some("zstd, gzip"), Some("zstd")),
+        // Explicit q-values rank the client's actual preference, regardless
+        // of the br/zstd/gzip tie-break order.
+        (Some("gzip;q=1.0, br;q=0.1"), Some("gzip")),
+        (Some("zstd;q=0.5, br;q=0.1, gzip;q=0.9"), Some("gzip")),
+        // A wildcard exclusion rejects every encoding not named explicitly.
+        (Some("identity;q=1, *;q=0"), None),
+        // No header at all means every encoding is acceptable at q=1.
+        (None, Some("br")),
+    ];
+
+    for (accept_encoding, expected) in cases {
+        let mut request = client.get("/compressed/other/hello.txt");
+        if let Some(header) = accept_encoding {
+            request.add_header(Header::new("Accept-Encoding", *header));
+        }
+
+        let response = request.dispatch();
+        assert_eq!(response.status(), Status::Ok, "Accept-Encoding: {accept_encoding:?}");
+        assert_eq!(
+            response.headers().get_one("Content-Encoding"), *expected,
+            "Accept-Encoding: {accept_encoding:?}"
+        );
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+    }
+}